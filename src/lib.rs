@@ -14,6 +14,10 @@ and `Deserialize` traits.
 #[cfg(test)]
 mod tests;
 
+pub mod sparse;
+
+pub use sparse::SparseBitMatrix;
+
 use std::{
 	fmt,
 	ops::{Index, IndexMut}
@@ -24,6 +28,7 @@ use bitvec::{
 	slice::{BitSlice, ChunksExact, ChunksExactMut, Iter, IterMut},
 	boxed::BitBox,
 	vec::BitVec,
+	field::BitField,
 };
 
 #[cfg(feature = "serde")]
@@ -155,6 +160,641 @@ impl BitMatrix {
 	pub fn set_all(&mut self, value: bool) {
 		self.storage.set_all(value);
 	}
+
+
+	/// OR row `read` into row `write`, in place.
+	/// Returns whether any bit in row `write` was changed.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 4);
+	/// matrix.set((0,1), true);
+	/// assert_eq!(matrix.union_rows(0, 1), true);
+	/// assert_eq!(matrix[(1,1)], true);
+	/// assert_eq!(matrix.union_rows(0, 1), false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `read` or `write` are out of bounds.
+	pub fn union_rows(&mut self, read: usize, write: usize) -> bool {
+		assert!(read < self.height, "row index out of bounds");
+		assert!(write < self.height, "row index out of bounds");
+
+		if read == write {
+			return false;
+		}
+
+		let read_row = self[read].to_bitvec();
+		let write_row = &mut self[write];
+		let before = write_row.to_bitvec();
+
+		*write_row |= read_row;
+
+		*write_row != before
+	}
+
+
+	/// AND row `read` into row `write`, in place.
+	/// Returns whether any bit in row `write` was changed.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 4);
+	/// matrix.set((1,0), true);
+	/// matrix.set((1,1), true);
+	/// assert_eq!(matrix.intersect_row(0, 1), true);
+	/// assert_eq!(matrix[(1,0)], false);
+	/// assert_eq!(matrix[(1,1)], false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `read` or `write` are out of bounds.
+	pub fn intersect_row(&mut self, read: usize, write: usize) -> bool {
+		assert!(read < self.height, "row index out of bounds");
+		assert!(write < self.height, "row index out of bounds");
+
+		if read == write {
+			return false;
+		}
+
+		let read_row = self[read].to_bitvec();
+		let write_row = &mut self[write];
+		let before = write_row.to_bitvec();
+
+		*write_row &= read_row;
+
+		*write_row != before
+	}
+
+
+	/// Clear every bit in row `write` that is set in row `read`.
+	/// Returns whether any bit in row `write` was changed.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 4);
+	/// matrix.set((0,1), true);
+	/// matrix.set((1,1), true);
+	/// matrix.set((1,2), true);
+	/// assert_eq!(matrix.subtract_row(0, 1), true);
+	/// assert_eq!(matrix[(1,1)], false);
+	/// assert_eq!(matrix[(1,2)], true);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `read` or `write` are out of bounds.
+	pub fn subtract_row(&mut self, read: usize, write: usize) -> bool {
+		assert!(read < self.height, "row index out of bounds");
+		assert!(write < self.height, "row index out of bounds");
+
+		if read == write {
+			let row = &mut self[write];
+			let changed = row.any();
+			row.set_all(false);
+			return changed;
+		}
+
+		let read_row = self[read].to_bitvec();
+		let write_row = &mut self[write];
+		let before = write_row.to_bitvec();
+
+		*write_row &= !read_row;
+
+		*write_row != before
+	}
+
+
+	/// Check whether every bit set in row `a` is also set in row `b`.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 4);
+	/// matrix.set((0,1), true);
+	/// matrix.set((1,1), true);
+	/// matrix.set((1,2), true);
+	/// assert_eq!(matrix.row_subset(0, 1), true);
+	/// assert_eq!(matrix.row_subset(1, 0), false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `a` or `b` are out of bounds.
+	pub fn row_subset(&self, a: usize, b: usize) -> bool {
+		self[a].iter().zip(self[b].iter()).all(|(a, b)| !*a || *b)
+	}
+
+
+	/// Compute the matrix product of `self` and `rhs` over GF(2), i.e. boolean matrix
+	/// multiplication where `C[i][j] = XOR over k of (A[i][k] & B[k][j])`.
+	///
+	/// Rather than the naive triple loop, each output row is built by XOR-ing together the
+	/// rows of `rhs` selected by the set bits of the corresponding row of `self`, turning the
+	/// inner loop into one row-wise XOR per set bit of `A`'s row.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut a = BitMatrix::new(2, 2);
+	/// a.set((0,0), true);
+	/// a.set((0,1), true);
+	/// a.set((1,1), true);
+	///
+	/// let mut b = BitMatrix::new(2, 2);
+	/// b.set((0,0), true);
+	/// b.set((1,1), true);
+	///
+	/// let c = a.mul_gf2(&b);
+	/// assert_eq!(c[(0,0)], true);
+	/// assert_eq!(c[(0,1)], true);
+	/// assert_eq!(c[(1,1)], true);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `self.width() != rhs.height()`.
+	pub fn mul_gf2(&self, rhs: &BitMatrix) -> BitMatrix {
+		assert_eq!(
+			self.width(), rhs.height(),
+			"incompatible dimensions for GF(2) matrix multiplication"
+		);
+
+		let mut result = BitMatrix::new(self.height(), rhs.width());
+
+		for i in 0 .. self.height() {
+			let mut acc: BitVec = BitVec::with_capacity(rhs.width());
+			acc.resize(rhs.width(), false);
+
+			for k in 0 .. self.width() {
+				if self[(i, k)] {
+					acc ^= rhs[k].iter().copied();
+				}
+			}
+
+			result[i].clone_from_bitslice(&acc);
+		}
+
+		result
+	}
+
+
+	/// Compute the transpose of the matrix.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 3);
+	/// matrix.set((0,1), true);
+	/// matrix.set((1,2), true);
+	///
+	/// let transposed = matrix.transpose();
+	/// assert_eq!(transposed.height(), 3);
+	/// assert_eq!(transposed.width(), 2);
+	/// assert_eq!(transposed[(1,0)], true);
+	/// assert_eq!(transposed[(2,1)], true);
+	/// ```
+	pub fn transpose(&self) -> BitMatrix {
+		let mut result = BitMatrix::new(self.width(), self.height());
+
+		for i in 0 .. self.height() {
+			for j in 0 .. self.width() {
+				if self[(i, j)] {
+					result.set((j, i), true);
+				}
+			}
+		}
+
+		result
+	}
+
+
+	/// Swap rows `a` and `b` in place.
+	fn swap_rows(&mut self, a: usize, b: usize) {
+		if a == b {
+			return;
+		}
+
+		for j in 0 .. self.width {
+			let va = self[(a, j)];
+			let vb = self[(b, j)];
+			self.set((a, j), vb);
+			self.set((b, j), va);
+		}
+	}
+
+
+	/// XOR row `read` into row `write`, in place.
+	fn xor_row_into(&mut self, read: usize, write: usize) {
+		if read == write {
+			self[write].set_all(false);
+			return;
+		}
+
+		let read_row = self[read].to_bitvec();
+		let write_row = &mut self[write];
+
+		*write_row ^= read_row;
+	}
+
+
+	/// Reduce the matrix to reduced row echelon form over GF(2), in place.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 2);
+	/// matrix.set((0,0), true);
+	/// matrix.set((0,1), true);
+	/// matrix.set((1,1), true);
+	///
+	/// matrix.row_reduce();
+	/// assert_eq!(matrix[(0,0)], true);
+	/// assert_eq!(matrix[(0,1)], false);
+	/// assert_eq!(matrix[(1,1)], true);
+	/// ```
+	pub fn row_reduce(&mut self) {
+		let mut pivot_row = 0;
+
+		for c in 0 .. self.width {
+			if pivot_row == self.height {
+				break;
+			}
+
+			let found = (pivot_row .. self.height).find(|&r| self[(r, c)]);
+
+			if let Some(r) = found {
+				self.swap_rows(pivot_row, r);
+
+				for r2 in 0 .. self.height {
+					if r2 != pivot_row && self[(r2, c)] {
+						self.xor_row_into(pivot_row, r2);
+					}
+				}
+
+				pivot_row += 1;
+			}
+		}
+	}
+
+
+	/// Compute the rank of the matrix over GF(2), i.e. the number of linearly independent rows.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 2);
+	/// matrix.set((0,0), true);
+	/// matrix.set((1,0), true);
+	/// assert_eq!(matrix.rank(), 1);
+	/// ```
+	pub fn rank(&self) -> usize {
+		let mut reduced = self.clone();
+		reduced.row_reduce();
+
+		(0 .. reduced.height).filter(|&r| reduced[r].any()).count()
+	}
+
+
+	/// Solve the linear system `self * x = b` over GF(2), returning one solution if the
+	/// system is consistent, or `None` if it has no solution.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut a = BitMatrix::new(2, 2);
+	/// a.set((0,0), true);
+	/// a.set((1,1), true);
+	///
+	/// let mut b = BitMatrix::new(1, 2);
+	/// b.set((0,0), true);
+	///
+	/// let x = a.solve(&b[0]).unwrap();
+	/// assert_eq!(x[0], true);
+	/// assert_eq!(x[1], false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `b.len() != self.height()`.
+	pub fn solve(&self, b: &BitSlice) -> Option<BitVec> {
+		assert_eq!(b.len(), self.height(), "right-hand side length must match matrix height");
+
+		let mut augmented = BitMatrix::new(self.height(), self.width() + 1);
+
+		for i in 0 .. self.height() {
+			for j in 0 .. self.width() {
+				augmented.set((i, j), self[(i, j)]);
+			}
+
+			augmented.set((i, self.width()), b[i]);
+		}
+
+		augmented.row_reduce();
+
+		for i in 0 .. augmented.height() {
+			let lhs_empty = (0 .. self.width()).all(|j| !augmented[(i, j)]);
+
+			if lhs_empty && augmented[(i, self.width())] {
+				return None;
+			}
+		}
+
+		let mut x = BitVec::with_capacity(self.width());
+		x.resize(self.width(), false);
+
+		let mut pivot_row = 0;
+
+		for c in 0 .. self.width() {
+			if pivot_row < augmented.height() && augmented[(pivot_row, c)] {
+				x.set(c, augmented[(pivot_row, self.width())]);
+				pivot_row += 1;
+			}
+		}
+
+		Some(x)
+	}
+
+
+	/// Iterate over the indices of the set columns in row `i`, in increasing order.
+	///
+	/// This scans the row in `usize`-sized chunks rather than bit-by-bit: for each non-zero
+	/// chunk, the lowest set bit is repeatedly extracted and cleared via
+	/// `word.trailing_zeros()` / `word &= word - 1`, which is far cheaper than filtering
+	/// [`iter`](Self::iter) when the row is sparse.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(1, 5);
+	/// matrix.set((0,1), true);
+	/// matrix.set((0,3), true);
+	/// let ones: Vec<usize> = matrix.row_ones(0).collect();
+	/// assert_eq!(ones, vec![1, 3]);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `i` is out of bounds.
+	pub fn row_ones(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+		const WORD_BITS: usize = usize::BITS as usize;
+
+		self[i]
+			.chunks(WORD_BITS)
+			.enumerate()
+			.flat_map(|(chunk_ix, chunk)| {
+				let mut word: usize = chunk.load_le();
+				let base = chunk_ix * WORD_BITS;
+
+				std::iter::from_fn(move || {
+					if word == 0 {
+						None
+					}
+					else {
+						let bit = word.trailing_zeros() as usize;
+						word &= word - 1;
+						Some(base + bit)
+					}
+				})
+			})
+	}
+
+
+	/// Count the number of set bits in the whole matrix.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 2);
+	/// matrix.set((0,0), true);
+	/// matrix.set((1,1), true);
+	/// assert_eq!(matrix.count_ones(), 2);
+	/// ```
+	pub fn count_ones(&self) -> usize {
+		self.storage.count_ones()
+	}
+
+
+	/// Count the number of set bits in row `i`.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 2);
+	/// matrix.set((0,0), true);
+	/// matrix.set((0,1), true);
+	/// assert_eq!(matrix.row_count_ones(0), 2);
+	/// assert_eq!(matrix.row_count_ones(1), 0);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `i` is out of bounds.
+	pub fn row_count_ones(&self, i: usize) -> usize {
+		self[i].count_ones()
+	}
+
+
+	/// Grow the matrix to `new_height` rows, appending zeroed rows.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 3);
+	/// matrix.set((1,2), true);
+	/// matrix.grow_rows(4);
+	/// assert_eq!(matrix.height(), 4);
+	/// assert_eq!(matrix[(1,2)], true);
+	/// assert_eq!(matrix[(3,2)], false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `new_height < self.height()`.
+	pub fn grow_rows(&mut self, new_height: usize) {
+		assert!(new_height >= self.height, "grow_rows cannot shrink the matrix");
+		self.resize(new_height, self.width);
+	}
+
+
+	/// Grow the matrix to `new_width` columns, appending zeroed columns.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 3);
+	/// matrix.set((1,2), true);
+	/// matrix.grow_cols(5);
+	/// assert_eq!(matrix.width(), 5);
+	/// assert_eq!(matrix[(1,2)], true);
+	/// assert_eq!(matrix[(1,4)], false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `new_width < self.width()`.
+	pub fn grow_cols(&mut self, new_width: usize) {
+		assert!(new_width >= self.width, "grow_cols cannot shrink the matrix");
+		self.resize(self.height, new_width);
+	}
+
+
+	/// Resize the matrix to `height` rows and `width` columns, preserving the values of the
+	/// overlapping region and zeroing any newly added bits.
+	///
+	/// Because storage is a single row-major `BitBox`, changing the width relocates every
+	/// existing row to its new stride, so this allocates a fresh backing buffer.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(3, 3);
+	/// matrix.set((2,2), true);
+	/// matrix.resize(2, 2);
+	/// assert_eq!(matrix.height(), 2);
+	/// assert_eq!(matrix.width(), 2);
+	/// ```
+	pub fn resize(&mut self, height: usize, width: usize) {
+		if height == self.height && width == self.width {
+			return;
+		}
+
+		let copy_height = self.height.min(height);
+		let copy_width = self.width.min(width);
+
+		let mut new = BitMatrix::new(height, width);
+
+		for i in 0 .. copy_height {
+			new[i][.. copy_width].clone_from_bitslice(&self[i][.. copy_width]);
+		}
+
+		*self = new;
+	}
+
+
+	/// Encode the matrix into a compact, bit-packed byte representation, independent of the
+	/// `serde` derive on this type.
+	///
+	/// The format is a varint-encoded `height`, a varint-encoded `width`, followed by the
+	/// matrix's bits packed LSB-first into bytes with no per-row padding
+	/// (`ceil(height * width / 8)` bytes total). This is portable across `usize` word sizes
+	/// and endianness, unlike serializing the backing `BitBox<Lsb0, usize>` directly.
+	///
+	/// ```
+	/// # use bitmatrix::BitMatrix;
+	/// let mut matrix = BitMatrix::new(2, 3);
+	/// matrix.set((0,1), true);
+	/// matrix.set((1,2), true);
+	///
+	/// let bytes = matrix.to_packed_bytes();
+	/// let decoded = BitMatrix::from_packed_bytes(&bytes).unwrap();
+	/// assert_eq!(decoded, matrix);
+	/// ```
+	pub fn to_packed_bytes(&self) -> Vec<u8> {
+		let mut out = Vec::new();
+
+		write_varint(&mut out, self.height as u64);
+		write_varint(&mut out, self.width as u64);
+
+		let mut byte = 0u8;
+		let mut bits_in_byte = 0u32;
+
+		for bit in self.iter() {
+			if *bit {
+				byte |= 1 << bits_in_byte;
+			}
+
+			bits_in_byte += 1;
+
+			if bits_in_byte == 8 {
+				out.push(byte);
+				byte = 0;
+				bits_in_byte = 0;
+			}
+		}
+
+		if bits_in_byte > 0 {
+			out.push(byte);
+		}
+
+		out
+	}
+
+
+	/// Decode a matrix previously encoded with [`to_packed_bytes`](Self::to_packed_bytes).
+	///
+	/// # Errors
+	/// Returns [`PackedBytesError::InvalidHeader`] if the decoded `height`/`width` overflow
+	/// when multiplied together, and [`PackedBytesError::UnexpectedEnd`] if `bytes` ends
+	/// before all the expected header or bit data has been read.
+	pub fn from_packed_bytes(bytes: &[u8]) -> Result<Self, PackedBytesError> {
+		let mut pos = 0;
+
+		let height = read_varint(bytes, &mut pos)? as usize;
+		let width = read_varint(bytes, &mut pos)? as usize;
+
+		let total_bits = height.checked_mul(width).ok_or(PackedBytesError::InvalidHeader)?;
+		let required_bytes = total_bits / 8 + (total_bits % 8 != 0) as usize;
+
+		if bytes.len() - pos < required_bytes {
+			return Err(PackedBytesError::UnexpectedEnd);
+		}
+
+		let mut matrix = BitMatrix::new(height, width);
+
+		for bit_ix in 0 .. total_bits {
+			let byte = bytes[pos + bit_ix / 8];
+			let bit = (byte >> (bit_ix % 8)) & 1 != 0;
+
+			if bit {
+				matrix.set((bit_ix / width, bit_ix % width), true);
+			}
+		}
+
+		Ok(matrix)
+	}
+}
+
+
+/// Error returned by [`BitMatrix::from_packed_bytes`] when the input is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackedBytesError {
+	/// The byte slice ended before all expected header or bit data was read.
+	UnexpectedEnd,
+	/// The decoded `height` and `width` overflow when multiplied together.
+	InvalidHeader,
+}
+
+
+impl fmt::Display for PackedBytesError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			PackedBytesError::UnexpectedEnd => write!(f, "unexpected end of packed bitmatrix bytes"),
+			PackedBytesError::InvalidHeader => write!(f, "packed bitmatrix header describes a matrix too large to represent"),
+		}
+	}
+}
+
+
+impl std::error::Error for PackedBytesError {}
+
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+	loop {
+		let mut byte = (value & 0x7f) as u8;
+		value >>= 7;
+
+		if value != 0 {
+			byte |= 0x80;
+		}
+
+		out.push(byte);
+
+		if value == 0 {
+			break;
+		}
+	}
+}
+
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, PackedBytesError> {
+	let mut value = 0u64;
+	let mut shift = 0;
+
+	loop {
+		let byte = *bytes.get(*pos).ok_or(PackedBytesError::UnexpectedEnd)?;
+		*pos += 1;
+
+		value |= ((byte & 0x7f) as u64) << shift;
+
+		if byte & 0x80 == 0 {
+			break;
+		}
+
+		shift += 7;
+	}
+
+	Ok(value)
 }
 
 
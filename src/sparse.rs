@@ -0,0 +1,205 @@
+/*!
+A sparse companion to [`BitMatrix`](crate::BitMatrix), for matrices whose rows are mostly
+empty. Only populated rows are stored, each as a sorted set of column indices, which is far
+more memory efficient than a dense `height * width` bit vector when the matrix is sparse.
+*/
+
+use std::collections::BTreeMap;
+
+use crate::BitMatrix;
+
+
+/// A sorted set of column indices for a single sparse row.
+pub type SparseRow = std::collections::BTreeSet<usize>;
+
+
+/// A sparse matrix of bits, storing only the populated rows.
+///
+/// Unlike [`BitMatrix`], which allocates `height * width` bits up front, a `SparseBitMatrix`
+/// only pays for the rows and columns that are actually set. This makes it a good fit for
+/// large, sparse adjacency-style matrices, at the cost of slower per-bit access.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseBitMatrix {
+	rows: BTreeMap<usize, SparseRow>,
+	height: usize,
+	width: usize,
+}
+
+
+impl SparseBitMatrix {
+	/// Create a `SparseBitMatrix` with the given size. All bits are initialized to `false`.
+	/// ```
+	/// # use bitmatrix::sparse::SparseBitMatrix;
+	/// let matrix = SparseBitMatrix::new(5, 10);
+	/// ```
+	pub fn new(height: usize, width: usize) -> Self {
+		Self {
+			rows: BTreeMap::new(),
+			height,
+			width,
+		}
+	}
+
+
+	/// Get the matrix height.
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	/// Get the matrix width.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+
+	/// Set the bit at `(i, j)`. Returns whether the bit was changed, i.e. whether it was
+	/// previously unset.
+	///
+	/// ```
+	/// # use bitmatrix::sparse::SparseBitMatrix;
+	/// let mut matrix = SparseBitMatrix::new(3, 11);
+	/// assert_eq!(matrix.insert((1,2)), true);
+	/// assert_eq!(matrix.insert((1,2)), false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `i` or `j` are out of bounds.
+	pub fn insert(&mut self, (i, j): (usize, usize)) -> bool {
+		assert!(i < self.height, "row index out of bounds");
+		assert!(j < self.width, "column index out of bounds");
+
+		self.rows.entry(i).or_insert_with(SparseRow::new).insert(j)
+	}
+
+
+	/// Check whether the bit at `(i, j)` is set.
+	///
+	/// ```
+	/// # use bitmatrix::sparse::SparseBitMatrix;
+	/// let mut matrix = SparseBitMatrix::new(3, 11);
+	/// matrix.insert((1,2));
+	/// assert_eq!(matrix.contains((1,2)), true);
+	/// assert_eq!(matrix.contains((1,3)), false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `i` or `j` are out of bounds.
+	pub fn contains(&self, (i, j): (usize, usize)) -> bool {
+		assert!(i < self.height, "row index out of bounds");
+		assert!(j < self.width, "column index out of bounds");
+
+		self.rows.get(&i).map_or(false, |row| row.contains(&j))
+	}
+
+
+	/// Iterate over the set columns of row `i`, in increasing order.
+	///
+	/// ```
+	/// # use bitmatrix::sparse::SparseBitMatrix;
+	/// let mut matrix = SparseBitMatrix::new(3, 11);
+	/// matrix.insert((1,5));
+	/// matrix.insert((1,2));
+	/// let columns: Vec<usize> = matrix.iter_row(1).collect();
+	/// assert_eq!(columns, vec![2, 5]);
+	/// ```
+	pub fn iter_row(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+		self.rows
+			.get(&i)
+			.into_iter()
+			.flat_map(|row| row.iter().copied())
+	}
+
+
+	/// Merge row `read` into row `write`, in place, via a sorted merge of their column sets.
+	/// Returns whether any column was added to row `write`.
+	///
+	/// ```
+	/// # use bitmatrix::sparse::SparseBitMatrix;
+	/// let mut matrix = SparseBitMatrix::new(2, 4);
+	/// matrix.insert((0,1));
+	/// assert_eq!(matrix.union_rows(0, 1), true);
+	/// assert_eq!(matrix.contains((1,1)), true);
+	/// assert_eq!(matrix.union_rows(0, 1), false);
+	/// ```
+	///
+	/// # Panics
+	/// Panics if `read` or `write` are out of bounds.
+	pub fn union_rows(&mut self, read: usize, write: usize) -> bool {
+		assert!(read < self.height, "row index out of bounds");
+		assert!(write < self.height, "row index out of bounds");
+
+		if read == write {
+			return false;
+		}
+
+		let additions: Vec<usize> = match (self.rows.get(&read), self.rows.get(&write)) {
+			(None, _) => return false,
+			(Some(read_row), None) => read_row.iter().copied().collect(),
+			(Some(read_row), Some(write_row)) => {
+				let mut write_iter = write_row.iter().copied().peekable();
+				read_row
+					.iter()
+					.copied()
+					.filter(|&j| {
+						while write_iter.peek().map_or(false, |&w| w < j) {
+							write_iter.next();
+						}
+						write_iter.peek() != Some(&j)
+					})
+					.collect()
+			},
+		};
+
+		if additions.is_empty() {
+			return false;
+		}
+
+		let write_row = self.rows.entry(write).or_insert_with(SparseRow::new);
+		for j in additions {
+			write_row.insert(j);
+		}
+
+		true
+	}
+
+
+	/// Build a dense [`BitMatrix`] with the same shape and bits set.
+	///
+	/// ```
+	/// # use bitmatrix::sparse::SparseBitMatrix;
+	/// let mut sparse = SparseBitMatrix::new(2, 4);
+	/// sparse.insert((1,2));
+	/// let dense = sparse.to_dense();
+	/// assert_eq!(dense[(1,2)], true);
+	/// assert_eq!(dense[(0,0)], false);
+	/// ```
+	pub fn to_dense(&self) -> BitMatrix {
+		let mut dense = BitMatrix::new(self.height, self.width);
+
+		for (&i, row) in &self.rows {
+			for &j in row {
+				dense.set((i, j), true);
+			}
+		}
+
+		dense
+	}
+}
+
+
+impl From<&BitMatrix> for SparseBitMatrix {
+	/// Build a `SparseBitMatrix` from a dense [`BitMatrix`], keeping only the set bits.
+	fn from(dense: &BitMatrix) -> Self {
+		let mut sparse = SparseBitMatrix::new(dense.height(), dense.width());
+
+		for i in 0 .. dense.height() {
+			for j in 0 .. dense.width() {
+				if dense[(i, j)] {
+					sparse.insert((i, j));
+				}
+			}
+		}
+
+		sparse
+	}
+}
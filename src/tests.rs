@@ -31,3 +31,297 @@ fn test_out_of_bounds_ix_set() {
 	let mut matrix = BitMatrix::new(5, 7);
 	*matrix[1].get_mut(10).unwrap() = true;
 }
+
+
+#[test]
+#[should_panic]
+fn test_sparse_out_of_bounds_insert() {
+	let mut matrix = super::sparse::SparseBitMatrix::new(5, 7);
+	matrix.insert((5, 1));
+}
+
+
+#[test]
+#[should_panic]
+fn test_sparse_out_of_bounds_contains() {
+	let matrix = super::sparse::SparseBitMatrix::new(5, 7);
+	matrix.contains((1, 7));
+}
+
+
+#[test]
+fn test_sparse_iter_row_on_absent_row() {
+	let matrix = super::sparse::SparseBitMatrix::new(5, 7);
+	let columns: Vec<usize> = matrix.iter_row(3).collect();
+	assert!(columns.is_empty());
+}
+
+
+#[test]
+fn test_sparse_union_rows_merges_sorted() {
+	let mut matrix = super::sparse::SparseBitMatrix::new(2, 4);
+	matrix.insert((0, 1));
+	matrix.insert((0, 3));
+	matrix.insert((1, 1));
+	matrix.insert((1, 2));
+
+	assert_eq!(matrix.union_rows(0, 1), true);
+
+	let columns: Vec<usize> = matrix.iter_row(1).collect();
+	assert_eq!(columns, vec![1, 2, 3]);
+
+	assert_eq!(matrix.union_rows(0, 1), false);
+}
+
+
+#[test]
+fn test_sparse_union_rows_absent_read_is_noop() {
+	let mut matrix = super::sparse::SparseBitMatrix::new(2, 4);
+	matrix.insert((1, 0));
+
+	assert_eq!(matrix.union_rows(0, 1), false);
+	let columns: Vec<usize> = matrix.iter_row(1).collect();
+	assert_eq!(columns, vec![0]);
+}
+
+
+#[test]
+fn test_sparse_union_rows_same_row_is_noop() {
+	let mut matrix = super::sparse::SparseBitMatrix::new(2, 4);
+	matrix.insert((0, 1));
+
+	assert_eq!(matrix.union_rows(0, 0), false);
+}
+
+
+#[test]
+fn test_sparse_to_dense_round_trip() {
+	let mut sparse = super::sparse::SparseBitMatrix::new(3, 5);
+	sparse.insert((0, 4));
+	sparse.insert((2, 0));
+
+	let dense = sparse.to_dense();
+	assert_eq!(super::sparse::SparseBitMatrix::from(&dense), sparse);
+}
+
+
+#[test]
+fn test_from_packed_bytes_unexpected_end() {
+	let matrix = BitMatrix::new(3, 7);
+	let mut bytes = matrix.to_packed_bytes();
+	bytes.pop();
+
+	assert_eq!(BitMatrix::from_packed_bytes(&bytes), Err(PackedBytesError::UnexpectedEnd));
+}
+
+
+#[test]
+fn test_from_packed_bytes_header_overflow() {
+	// varint-encoded height = width = u64::MAX, which overflows on multiplication.
+	let mut bytes = Vec::new();
+	write_varint(&mut bytes, u64::MAX);
+	write_varint(&mut bytes, u64::MAX);
+
+	assert_eq!(BitMatrix::from_packed_bytes(&bytes), Err(PackedBytesError::InvalidHeader));
+}
+
+
+#[test]
+fn test_from_packed_bytes_header_too_large_for_input() {
+	// a plausible, non-overflowing header claiming far more bits than the input carries.
+	let mut bytes = Vec::new();
+	write_varint(&mut bytes, 1_000_000);
+	write_varint(&mut bytes, 1_000_000);
+
+	assert_eq!(BitMatrix::from_packed_bytes(&bytes), Err(PackedBytesError::UnexpectedEnd));
+}
+
+
+#[test]
+fn test_row_ops_cross_word_boundary() {
+	// width spans multiple `usize` words, so these exercise the split storage path.
+	let width = 2 * usize::BITS as usize + 3;
+	let mut matrix = BitMatrix::new(2, width);
+
+	matrix.set((0, 0), true);
+	matrix.set((0, usize::BITS as usize), true);
+	matrix.set((0, width - 1), true);
+
+	assert_eq!(matrix.union_rows(0, 1), true);
+	assert_eq!(matrix[(1, 0)], true);
+	assert_eq!(matrix[(1, usize::BITS as usize)], true);
+	assert_eq!(matrix[(1, width - 1)], true);
+	assert_eq!(matrix.union_rows(0, 1), false);
+
+	assert_eq!(matrix.row_subset(1, 0), true);
+
+	assert_eq!(matrix.subtract_row(0, 1), true);
+	assert!(!matrix[1].any());
+
+	matrix.set((1, 0), true);
+	matrix.set((1, 1), true);
+	assert_eq!(matrix.intersect_row(0, 1), true);
+	assert_eq!(matrix[(1, 0)], true);
+	assert_eq!(matrix[(1, 1)], false);
+	assert_eq!(matrix[(1, usize::BITS as usize)], false);
+}
+
+
+#[test]
+fn test_row_ops_same_row_is_noop() {
+	let mut matrix = BitMatrix::new(1, 4);
+	matrix.set((0, 1), true);
+
+	assert_eq!(matrix.union_rows(0, 0), false);
+	assert_eq!(matrix.intersect_row(0, 0), false);
+	assert_eq!(matrix[(0, 1)], true);
+}
+
+
+#[test]
+#[should_panic]
+fn test_union_rows_out_of_bounds() {
+	let mut matrix = BitMatrix::new(2, 0);
+	matrix.union_rows(0, 2);
+}
+
+
+#[test]
+#[should_panic]
+fn test_mul_gf2_dimension_mismatch() {
+	let a = BitMatrix::new(2, 3);
+	let b = BitMatrix::new(4, 2);
+	let _ = a.mul_gf2(&b);
+}
+
+
+#[test]
+fn test_mul_gf2_and_transpose_cross_word_boundary() {
+	let width = usize::BITS as usize + 1;
+	let mut identity = BitMatrix::new(width, width);
+
+	for i in 0 .. width {
+		identity.set((i, i), true);
+	}
+
+	let mut a = BitMatrix::new(width, width);
+	a.set((0, width - 1), true);
+	a.set((width - 1, 0), true);
+
+	let product = a.mul_gf2(&identity);
+	assert_eq!(product, a);
+
+	let transposed = a.transpose();
+	assert_eq!(transposed[(width - 1, 0)], true);
+	assert_eq!(transposed[(0, width - 1)], true);
+	assert_eq!(transposed.transpose(), a);
+}
+
+
+#[test]
+fn test_rank_deficient_matrix() {
+	// row 2 is the XOR of rows 0 and 1, so the matrix has rank 2, not 3.
+	let mut matrix = BitMatrix::new(3, 3);
+	matrix.set((0, 0), true);
+	matrix.set((1, 1), true);
+	matrix.set((2, 0), true);
+	matrix.set((2, 1), true);
+
+	assert_eq!(matrix.rank(), 2);
+}
+
+
+#[test]
+fn test_row_reduce_dependent_rows() {
+	let mut matrix = BitMatrix::new(3, 3);
+	matrix.set((0, 0), true);
+	matrix.set((1, 1), true);
+	matrix.set((2, 0), true);
+	matrix.set((2, 1), true);
+
+	matrix.row_reduce();
+
+	assert_eq!(matrix[(0, 0)], true);
+	assert_eq!(matrix[(1, 1)], true);
+	assert!(!matrix[2].any());
+}
+
+
+#[test]
+fn test_solve_inconsistent_system() {
+	// rows 0 and 1 are identical, but the right-hand side differs, so no `x` can satisfy both.
+	let mut a = BitMatrix::new(2, 2);
+	a.set((0, 0), true);
+	a.set((1, 0), true);
+
+	let mut b = BitMatrix::new(1, 2);
+	b.set((0, 0), true);
+
+	assert_eq!(a.solve(&b[0]), None);
+}
+
+
+#[test]
+fn test_row_ones_cross_word_boundary() {
+	let width = 2 * usize::BITS as usize + 3;
+	let mut matrix = BitMatrix::new(1, width);
+
+	matrix.set((0, 0), true);
+	matrix.set((0, usize::BITS as usize), true);
+	matrix.set((0, width - 1), true);
+
+	let ones: Vec<usize> = matrix.row_ones(0).collect();
+	assert_eq!(ones, vec![0, usize::BITS as usize, width - 1]);
+	assert_eq!(matrix.row_count_ones(0), 3);
+	assert_eq!(matrix.count_ones(), 3);
+}
+
+
+#[test]
+fn test_grow_rows_and_cols() {
+	let mut matrix = BitMatrix::new(2, 2);
+	matrix.set((1, 1), true);
+
+	matrix.grow_rows(4);
+	assert_eq!(matrix.height(), 4);
+	assert_eq!(matrix.width(), 2);
+	assert_eq!(matrix[(1, 1)], true);
+	assert!(!matrix[2].any());
+	assert!(!matrix[3].any());
+
+	matrix.grow_cols(3);
+	assert_eq!(matrix.height(), 4);
+	assert_eq!(matrix.width(), 3);
+	assert_eq!(matrix[(1, 1)], true);
+	assert_eq!(matrix[(1, 2)], false);
+}
+
+
+#[test]
+fn test_resize_relocates_rows_across_word_boundary() {
+	let old_width = usize::BITS as usize - 1;
+	let new_width = usize::BITS as usize + 1;
+
+	let mut matrix = BitMatrix::new(2, old_width);
+	matrix.set((0, old_width - 1), true);
+	matrix.set((1, 0), true);
+
+	matrix.resize(2, new_width);
+
+	assert_eq!(matrix.width(), new_width);
+	assert_eq!(matrix[(0, old_width - 1)], true);
+	assert_eq!(matrix[(1, 0)], true);
+	assert_eq!(matrix[(0, new_width - 1)], false);
+}
+
+
+#[test]
+fn test_resize_shrink_to_zero() {
+	let mut matrix = BitMatrix::new(3, 3);
+	matrix.set((2, 2), true);
+
+	matrix.resize(0, 0);
+
+	assert_eq!(matrix.height(), 0);
+	assert_eq!(matrix.width(), 0);
+}